@@ -1,7 +1,7 @@
 use std::collections::VecDeque;
 
 use crate::{
-    encoding::hybrid_rle::{self, HybridRleDecoder},
+    encoding::hybrid_rle::{self, HybridRleDecoder, HybridRleGatherer},
     indexes::Interval,
     page::{split_buffer, DataPage},
     read::levels::get_bit_width,
@@ -20,6 +20,83 @@ pub(super) fn dict_indices_decoder(page: &DataPage) -> hybrid_rle::HybridRleDeco
     hybrid_rle::HybridRleDecoder::new(indices_buffer, bit_width as u32, page.num_values())
 }
 
+/// A [`HybridRleGatherer`] that translates dictionary indices into `dict` entries as whole
+/// runs land, rather than looking `dict[index]` up one value at a time.
+///
+/// The index comes from an untrusted page: the bit width only bounds it to `2^num_bits - 1`,
+/// which can exceed `dict.len() - 1`, so an out-of-range index decodes to `T::default()`
+/// instead of panicking.
+struct DictionaryGatherer<'a, T> {
+    dict: &'a [T],
+    target: Vec<T>,
+}
+
+impl<'a, T: Clone + Default> HybridRleGatherer for DictionaryGatherer<'a, T> {
+    fn gather_repeated(&mut self, value: u32, count: usize) {
+        let item = self.dict.get(value as usize).cloned().unwrap_or_default();
+        self.target.extend(std::iter::repeat_n(item, count));
+    }
+
+    fn gather_bitpacked(&mut self, packed: &[u8], num_values: usize, num_bits: u8) {
+        self.target.extend((0..num_values).map(|i| {
+            let index = hybrid_rle::unpack_index(packed, num_bits as usize, i);
+            self.dict.get(index as usize).cloned().unwrap_or_default()
+        }));
+    }
+}
+
+/// Decodes the dictionary-indices page of `page` directly into `dict` entries, dispatching
+/// whole RLE/bit-packed runs to a [`DictionaryGatherer`] rather than materializing an
+/// intermediate vector of indices.
+pub(super) fn dict_indices_gather<T: Clone + Default>(page: &DataPage, dict: &[T]) -> Vec<T> {
+    let decoder = dict_indices_decoder(page);
+    let mut gatherer = DictionaryGatherer {
+        dict,
+        target: Vec::with_capacity(page.num_values()),
+    };
+    decoder.gather_into(&mut gatherer);
+    gatherer.target
+}
+
+/// The dictionary indices of every data page of a column sharing one dictionary, collected
+/// undecoded: `dict[index]` is not looked up until [`gather`](Self::gather) is called once
+/// for the whole column.
+#[derive(Debug, Default)]
+pub struct DelayedDictionaryIndices<'a> {
+    pages: Vec<HybridRleDecoder<'a>>,
+}
+
+impl<'a> DelayedDictionaryIndices<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queues `decoder`'s indices for [`gather`](Self::gather), without decoding them.
+    pub(super) fn push_decoder(&mut self, decoder: HybridRleDecoder<'a>) {
+        self.pages.push(decoder);
+    }
+
+    /// Parses (but does not decode) `page`'s dictionary indices and queues them for
+    /// [`gather`](Self::gather).
+    pub fn push(&mut self, page: &'a DataPage) {
+        self.push_decoder(dict_indices_decoder(page));
+    }
+
+    /// Consumes `self`, translating every queued page's indices into `dict` entries in a single
+    /// pass over one output buffer.
+    pub fn gather<T: Clone + Default>(self, dict: &[T]) -> Vec<T> {
+        let capacity = self.pages.iter().map(|page| page.size_hint().0).sum();
+        let mut gatherer = DictionaryGatherer {
+            dict,
+            target: Vec::with_capacity(capacity),
+        };
+        for decoder in self.pages {
+            decoder.gather_into(&mut gatherer);
+        }
+        gatherer.target
+    }
+}
+
 /// Decoder of definition levels.
 #[derive(Debug)]
 pub enum DefLevelsDecoder<'a> {
@@ -46,6 +123,15 @@ impl<'a> DefLevelsDecoder<'a> {
             Self::Levels(iter, max_def_level as u32)
         }
     }
+
+    /// Consumes `n` definition levels without decoding them, returning the number actually
+    /// skipped. See [`HybridRleDecoder::skip_in_place`].
+    pub fn skip_in_place(&mut self, n: usize) -> usize {
+        match self {
+            Self::Bitmap(iter) => iter.skip_in_place(n),
+            Self::Levels(iter, _) => iter.skip_in_place(n),
+        }
+    }
 }
 
 /// Iterator adapter to convert an iterator of non-null values and an iterator over validity
@@ -82,7 +168,11 @@ impl<T, V: Iterator<Item = bool>, I: Iterator<Item = T>> Iterator for OptionalVa
 /// those N items.
 ///
 /// This iterator is best used with iterators that implement `nth` since skipping items
-/// allows this iterator to skip sequences of items without having to call each of them.
+/// allows this iterator to skip sequences of items without having to call each of them. In
+/// particular, [`HybridRleDecoder`](crate::encoding::hybrid_rle::HybridRleDecoder) and
+/// [`bit_packed::Decoder`](crate::encoding::bit_packed::Decoder) override `nth` in terms of
+/// their own `skip_in_place`, so the hole between two selected intervals is skipped in
+/// O(runs)/O(packs) rather than O(values) for those inner iterators.
 #[derive(Debug, Clone)]
 pub struct SliceFilteredIter<I> {
     iter: I,
@@ -138,6 +228,105 @@ impl<T, I: Iterator<Item = T>> Iterator for SliceFilteredIter<I> {
 
 impl<I: ExactSizeIterator> ExactSizeIterator for SliceFilteredIter<I> {}
 
+/// Returns the position of the first bit in `mask`, starting at `from` and up to `len`, whose
+/// value is `want`, or `len` if there is none.
+fn next_bit(mask: &[u8], len: usize, from: usize, want: bool) -> usize {
+    (from..len)
+        .find(|&i| ((mask[i / 8] >> (i % 8)) & 1 == 1) == want)
+        .unwrap_or(len)
+}
+
+/// Converts a bit-packed boolean `mask` of `len` values into the [`Interval`] representation
+/// used by [`SliceFilteredIter`], one `Interval` per maximal run of set bits.
+///
+/// This is cheapest when `mask` has few, long runs; a mask that flips often is better driven
+/// directly through [`MaskFilteredIter`], which skips the `VecDeque` this builds.
+pub fn mask_to_intervals(mask: &[u8], len: usize) -> VecDeque<Interval> {
+    let mut intervals = VecDeque::new();
+    let mut pos = 0;
+    while pos < len {
+        let start = next_bit(mask, len, pos, true);
+        if start == len {
+            break;
+        }
+        let end = next_bit(mask, len, start, false);
+        intervals.push_back(Interval::new(start, end - start));
+        pos = end;
+    }
+    intervals
+}
+
+/// An iterator adapter that filters an iterator of items by a bit-packed boolean mask, yielding
+/// only the items at set positions.
+///
+/// This complements [`SliceFilteredIter`]: where that type selects by a handful of contiguous
+/// [`Interval`]s, this is suited to masks with many short, scattered runs that would otherwise
+/// explode into a large `VecDeque<Interval>`. Runs of unset bits are skipped with a single
+/// `nth`/`skip_in_place` advance; runs of set bits are still read back one value at a time.
+#[derive(Debug, Clone)]
+pub struct MaskFilteredIter<'a, I> {
+    iter: I,
+    mask: &'a [u8],
+    len: usize,
+    total: usize, // number of set bits not yet yielded, a cache
+    pos: usize,   // position in the mask that `self.iter` is currently aligned to
+    current_remaining: usize,
+}
+
+impl<'a, I> MaskFilteredIter<'a, I> {
+    /// Returns a new [`MaskFilteredIter`] that yields the items of `iter` at the positions
+    /// where `mask`, a bit-packed boolean mask of `len` values, is set.
+    pub fn new(iter: I, mask: &'a [u8], len: usize) -> Self {
+        let total = (0..len)
+            .filter(|&i| (mask[i / 8] >> (i % 8)) & 1 == 1)
+            .count();
+        Self {
+            iter,
+            mask,
+            len,
+            total,
+            pos: 0,
+            current_remaining: 0,
+        }
+    }
+}
+
+impl<'a, T, I: Iterator<Item = T>> Iterator for MaskFilteredIter<'a, I> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.current_remaining == 0 {
+            if self.pos >= self.len {
+                return None;
+            }
+            let start = next_bit(self.mask, self.len, self.pos, true);
+            if start == self.len {
+                self.pos = self.len;
+                return None;
+            }
+            // skip the hole between the previous position and this run of set bits
+            let item = self.iter.nth(start - self.pos);
+            let end = next_bit(self.mask, self.len, start, false);
+            self.current_remaining = end - start - 1;
+            self.pos = end;
+            self.total -= 1;
+            item
+        } else {
+            self.current_remaining -= 1;
+            self.total -= 1;
+            self.iter.next()
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (min, max) = self.iter.size_hint();
+        (min.min(self.total), max.map(|x| x.min(self.total)))
+    }
+}
+
+impl<'a, I: ExactSizeIterator> ExactSizeIterator for MaskFilteredIter<'a, I> {}
+
 #[cfg(test)]
 mod test {
     use std::collections::VecDeque;
@@ -185,4 +374,92 @@ mod test {
         let expected = 2 + 11 + 1 - 3;
         assert_eq!(iter.size_hint(), (expected, Some(expected)))
     }
+
+    #[test]
+    fn mask_basic() {
+        let iter = 0..=100;
+
+        // set bits at 0, 1, 20..=31
+        let mask = [0b0000_0011u8, 0, 0b1111_0000, 0b1111_1111];
+
+        let a = MaskFilteredIter::new(iter, &mask, 32);
+
+        let expected: Vec<usize> = vec![0, 1].into_iter().chain(20..=31).collect();
+
+        assert_eq!(expected, a.collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn mask_size_hint() {
+        let iter = 0..101;
+
+        // set bits at 0, 1, 20..=31 (14 values total)
+        let mask = [0b0000_0011u8, 0, 0b1111_0000, 0b1111_1111];
+
+        let mut iter = MaskFilteredIter::new(iter, &mask, 32);
+        assert_eq!(iter.len(), 14);
+
+        iter.next();
+        iter.next();
+        iter.next();
+
+        let expected = 14 - 3;
+        assert_eq!(iter.size_hint(), (expected, Some(expected)));
+        assert_eq!(iter.len(), expected);
+    }
+
+    #[test]
+    fn mask_to_intervals_matches_mask_filtered_iter() {
+        let mask = [0b0000_0011u8, 0, 0b1111_0000, 0b1111_1111];
+        let len = 32;
+
+        let via_intervals: Vec<usize> =
+            SliceFilteredIter::new(0..len, mask_to_intervals(&mask, len)).collect();
+        let via_mask: Vec<usize> = MaskFilteredIter::new(0..len, &mask, len).collect();
+
+        assert_eq!(via_intervals, via_mask);
+    }
+
+    #[test]
+    fn delayed_dictionary_indices_gather_spans_pages() {
+        let dict = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+
+        // indicator = 4 << 1 = 8 (run length 4, RLE), index 1
+        let page_a = vec![8u8, 1];
+        // indicator = 3 << 1 = 6 (run length 3, RLE), index 2
+        let page_b = vec![6u8, 2];
+        // indicator = (1 << 1) | 1 = 3 (bit-packed, 1 group of 8), 2 bits per index:
+        // indices 1,0,1,2,0,2,1,0
+        let page_c = vec![3u8, 0x91, 0x18];
+
+        let mut pages = DelayedDictionaryIndices::default();
+        pages.push_decoder(HybridRleDecoder::new(&page_a, 2, 4));
+        pages.push_decoder(HybridRleDecoder::new(&page_b, 2, 3));
+        pages.push_decoder(HybridRleDecoder::new(&page_c, 2, 8));
+
+        let gathered = pages.gather(&dict);
+
+        let expected = vec![
+            "b", "b", "b", "b", "c", "c", "c", "b", "a", "b", "c", "a", "c", "b", "a",
+        ];
+        assert_eq!(gathered, expected);
+    }
+
+    #[test]
+    fn dictionary_gatherer_out_of_range_index_defaults() {
+        let dict = vec!["a".to_string(), "b".to_string()];
+
+        // indicator = (1 << 1) | 1 = 3 (bit-packed, 1 group of 8), 2 bits per index:
+        // indices 1,3,0,1,1,1,1,1 -- index 3 is out of range for a 2-entry dict.
+        let page = vec![3u8, 0x4D, 0x55];
+        let mut pages = DelayedDictionaryIndices::default();
+        pages.push_decoder(HybridRleDecoder::new(&page, 2, 8));
+
+        let gathered = pages.gather(&dict);
+        let expected: Vec<String> = vec!["b", "", "a", "b", "b", "b", "b", "b"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+        assert_eq!(gathered, expected);
+    }
 }
@@ -95,6 +95,130 @@ impl<'a> Decoder<'a> {
     }
 }
 
+impl<'a> Decoder<'a> {
+    /// Decodes as many values as fit in `out`, returning the number of values written.
+    ///
+    /// Whole 32-value blocks are unpacked directly into `out`, paying the per-value branch
+    /// and bounds check once per block instead of once per value; the final, partial block
+    /// still falls back to an element-wise copy.
+    pub fn decode_batch(&mut self, out: &mut [u32]) -> usize {
+        let length = out.len().min(self.remaining);
+        let out = &mut out[..length];
+        let mut written = 0;
+
+        // Drain whatever is left of the already-decoded `current_pack`, element-wise, to
+        // realign `out` on a pack boundary before switching to the bulk path below.
+        while self.current_pack_index < BLOCK_LEN && written < length {
+            out[written] = self.current_pack[self.current_pack_index];
+            self.current_pack_index += 1;
+            written += 1;
+        }
+
+        if self.current_pack_index == BLOCK_LEN && written < length {
+            // `current_pack` is now fully drained: decode whole blocks straight into
+            // aligned chunks of `out`, advancing `compressed_chunks` directly without
+            // touching `current_pack_index`.
+            let mut chunks = out[written..].chunks_exact_mut(BLOCK_LEN);
+            for chunk in &mut chunks {
+                let compressed = self
+                    .compressed_chunks
+                    .next()
+                    .expect("enough compressed chunks for `length`");
+                let chunk: &mut [u32; BLOCK_LEN] = chunk.try_into().unwrap();
+                decode_pack(compressed, self.num_bits, chunk);
+                written += BLOCK_LEN;
+            }
+
+            let tail = chunks.into_remainder();
+            if !tail.is_empty() {
+                // the final, partial block: decode it into `current_pack` and copy it
+                // out element-wise, leaving `current_pack_index` at the next value.
+                let compressed = self
+                    .compressed_chunks
+                    .next()
+                    .expect("enough compressed chunks for `length`");
+                decode_pack(compressed, self.num_bits, &mut self.current_pack);
+                tail.copy_from_slice(&self.current_pack[..tail.len()]);
+                self.current_pack_index = tail.len();
+                written += tail.len();
+            }
+        }
+
+        if self.current_pack_index == BLOCK_LEN && self.remaining - written > 0 {
+            // we ended exactly on a pack boundary with values left: eagerly refill
+            // `current_pack`, as `Iterator::next` does. If there's no more compressed
+            // data, leave `current_pack_index` at `BLOCK_LEN` so a later read fails the
+            // same way `next` would on the same truncated input, instead of replaying
+            // `current_pack`'s stale contents.
+            if let Some(compressed) = self.compressed_chunks.next() {
+                decode_pack(compressed, self.num_bits, &mut self.current_pack);
+                self.current_pack_index = 0;
+            }
+        }
+
+        self.remaining -= written;
+        written
+    }
+
+    /// Consumes `n` logical values without decoding them, returning the number actually
+    /// skipped (capped by the values remaining).
+    ///
+    /// Whole packs are skipped by advancing `compressed_chunks` directly; only the single
+    /// partial pack the skip lands in (if any) is actually decoded, so skipping, say, 10k
+    /// rows is O(packs) rather than O(values).
+    pub fn skip_in_place(&mut self, n: usize) -> usize {
+        let n = n.min(self.remaining);
+        let mut skipped = 0;
+
+        // drain whatever is left of the already-decoded `current_pack`.
+        while self.current_pack_index < BLOCK_LEN && skipped < n {
+            self.current_pack_index += 1;
+            skipped += 1;
+        }
+
+        // `current_pack` is fully drained: skip whole packs without decoding them.
+        while self.current_pack_index == BLOCK_LEN && n - skipped >= BLOCK_LEN {
+            if self.compressed_chunks.next().is_none() {
+                self.remaining -= skipped;
+                return skipped;
+            }
+            skipped += BLOCK_LEN;
+        }
+
+        if self.current_pack_index == BLOCK_LEN {
+            let remainder = n - skipped;
+            if remainder > 0 {
+                // the skip lands inside this pack: it has to be decoded for later reads.
+                // If there's no more compressed data, stop here rather than pretending we
+                // skipped past values that were never there.
+                match self.compressed_chunks.next() {
+                    Some(chunk) => {
+                        decode_pack(chunk, self.num_bits, &mut self.current_pack);
+                        self.current_pack_index = remainder;
+                        skipped += remainder;
+                    }
+                    None => {
+                        self.remaining -= skipped;
+                        return skipped;
+                    }
+                }
+            } else if self.remaining - skipped > 0 {
+                // landed exactly on a pack boundary with values left: eagerly refill, as
+                // `next`/`decode_batch` do. If there's no more compressed data, leave
+                // `current_pack_index` at `BLOCK_LEN` so a later read fails the same way
+                // `next` would on the same truncated input.
+                if let Some(chunk) = self.compressed_chunks.next() {
+                    decode_pack(chunk, self.num_bits, &mut self.current_pack);
+                    self.current_pack_index = 0;
+                }
+            }
+        }
+
+        self.remaining -= skipped;
+        skipped
+    }
+}
+
 impl<'a> Iterator for Decoder<'a> {
     type Item = u32;
 
@@ -117,6 +241,11 @@ impl<'a> Iterator for Decoder<'a> {
     fn size_hint(&self) -> (usize, Option<usize>) {
         (self.remaining, Some(self.remaining))
     }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.skip_in_place(n);
+        self.next()
+    }
 }
 
 #[cfg(test)]
@@ -215,4 +344,133 @@ mod tests {
         let decoded = Decoder::new(&data, num_bits, length).collect::<Vec<_>>();
         assert_eq!(decoded, vec![255, 0, 1]);
     }
+
+    #[test]
+    fn decode_batch_matches_iterator() {
+        let num_bits = 3;
+        let length = 8 * 7;
+        let data = vec![
+            0b00000101u8,
+            0b00111001,
+            0b01110111,
+            0b00000101u8,
+            0b00111001,
+            0b01110111,
+            0b00000101u8,
+            0b00111001,
+            0b01110111,
+            0b00000101u8,
+            0b00111001,
+            0b01110111,
+            0b00000101u8,
+            0b00111001,
+            0b01110111,
+            0b00000101u8,
+            0b00111001,
+            0b01110111,
+            0b00000101u8,
+            0b00111001,
+            0b01110111,
+        ];
+
+        let expected = Decoder::new(&data, num_bits, length).collect::<Vec<_>>();
+
+        let mut decoder = Decoder::new(&data, num_bits, length);
+        let mut out = vec![0u32; length];
+        let written = decoder.decode_batch(&mut out);
+
+        assert_eq!(written, length);
+        assert_eq!(out, expected);
+        assert_eq!(decoder.next(), None);
+    }
+
+    #[test]
+    fn decode_batch_partial_then_rest() {
+        // length is not a multiple of BLOCK_LEN, and we ask for fewer values than remain.
+        let num_bits = 1;
+        let length = 4;
+        let data = vec![0b01100000u8];
+
+        let mut decoder = Decoder::new(&data, num_bits, length);
+        let mut out = [0u32; 2];
+        let written = decoder.decode_batch(&mut out);
+        assert_eq!(written, 2);
+        assert_eq!(out, [0, 1]);
+
+        // the remainder is still available through both APIs.
+        let rest = decoder.collect::<Vec<_>>();
+        assert_eq!(rest, vec![1, 0]);
+    }
+
+    #[test]
+    fn decode_batch_multiple_whole_blocks() {
+        // 4 whole 32-value blocks, exercising the `chunks_exact_mut` bulk path on its own.
+        let num_bits = 3;
+        let length = BLOCK_LEN * 4;
+        let data = [0b00000101u8, 0b00111001, 0b01110111]
+            .iter()
+            .cloned()
+            .cycle()
+            .take(3 * (length / 8))
+            .collect::<Vec<_>>();
+
+        let expected = Decoder::new(&data, num_bits, length).collect::<Vec<_>>();
+
+        let mut decoder = Decoder::new(&data, num_bits, length);
+        let mut out = vec![0u32; length];
+        let written = decoder.decode_batch(&mut out);
+
+        assert_eq!(written, length);
+        assert_eq!(out, expected);
+        assert_eq!(decoder.next(), None);
+    }
+
+    #[test]
+    fn decode_batch_exact_block_then_more() {
+        // `out` ends exactly on a pack boundary, but more values remain afterwards.
+        let num_bits = 3;
+        let length = BLOCK_LEN + 5;
+        let data = [0b00000101u8, 0b00111001, 0b01110111]
+            .iter()
+            .cloned()
+            .cycle()
+            .take(3 * length.div_ceil(8))
+            .collect::<Vec<_>>();
+
+        let expected = Decoder::new(&data, num_bits, length).collect::<Vec<_>>();
+
+        let mut decoder = Decoder::new(&data, num_bits, length);
+        let mut out = vec![0u32; BLOCK_LEN];
+        let written = decoder.decode_batch(&mut out);
+        assert_eq!(written, BLOCK_LEN);
+        assert_eq!(out, expected[..BLOCK_LEN]);
+
+        let rest = decoder.collect::<Vec<_>>();
+        assert_eq!(rest, expected[BLOCK_LEN..]);
+    }
+
+    #[test]
+    fn skip_in_place_matches_nth() {
+        let num_bits = 3;
+        let length = BLOCK_LEN * 2 + 5;
+        let data = [0b00000101u8, 0b00111001, 0b01110111]
+            .iter()
+            .cloned()
+            .cycle()
+            .take(3 * length.div_ceil(8))
+            .collect::<Vec<_>>();
+
+        for skip in [0, 1, 5, BLOCK_LEN, BLOCK_LEN + 3, BLOCK_LEN * 2, length, length + 10] {
+            let expected = Decoder::new(&data, num_bits, length)
+                .collect::<Vec<_>>()
+                .into_iter()
+                .skip(skip)
+                .collect::<Vec<_>>();
+
+            let mut decoder = Decoder::new(&data, num_bits, length);
+            let skipped = decoder.skip_in_place(skip);
+            assert_eq!(skipped, skip.min(length));
+            assert_eq!(decoder.collect::<Vec<_>>(), expected);
+        }
+    }
 }
@@ -0,0 +1,608 @@
+use std::collections::VecDeque;
+use std::io::Write;
+
+fn ceil8(value: usize) -> usize {
+    value.div_ceil(8)
+}
+
+fn uleb128_decode(values: &[u8]) -> (u64, usize) {
+    let mut result = 0u64;
+    let mut shift = 0;
+    let mut consumed = 0;
+    for byte in values {
+        consumed += 1;
+        result |= ((byte & 0x7F) as u64) << shift;
+        if byte & 0x80 == 0 {
+            break;
+        }
+        shift += 7;
+    }
+    (result, consumed)
+}
+
+fn uleb128_encode<W: Write>(writer: &mut W, mut value: u64) -> std::io::Result<()> {
+    loop {
+        let mut byte = (value & 0x7F) as u8;
+        value >>= 7;
+        if value != 0 {
+            byte |= 0x80;
+        }
+        writer.write_all(&[byte])?;
+        if value == 0 {
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// Unpacks the single value at logical position `index`, `num_bits` bits each, LSB-first,
+/// from `packed`. Used by consumers (e.g. dictionary gatherers) that translate each index as
+/// it is unpacked rather than materializing the whole run up front.
+pub(crate) fn unpack_index(packed: &[u8], num_bits: usize, index: usize) -> u32 {
+    if num_bits == 0 {
+        return 0;
+    }
+    let bit_pos = index * num_bits;
+    let byte_pos = bit_pos / 8;
+    let bit_offset = bit_pos % 8;
+
+    let mask = (1u64 << num_bits) - 1;
+    let mut word_bytes = [0u8; 8];
+    let available = packed.len().saturating_sub(byte_pos).min(8);
+    word_bytes[..available].copy_from_slice(&packed[byte_pos..byte_pos + available]);
+    let word = u64::from_le_bytes(word_bytes);
+
+    ((word >> bit_offset) & mask) as u32
+}
+
+/// Unpacks `out.len()` values, `num_bits` bits each, LSB-first, from `packed`.
+fn unpack_bitpacked(packed: &[u8], num_bits: usize, out: &mut [u32]) {
+    out.iter_mut()
+        .enumerate()
+        .for_each(|(i, slot)| *slot = unpack_index(packed, num_bits, i));
+}
+
+/// A single run of the hybrid RLE/bit-packed encoding
+/// (https://github.com/apache/parquet-format/blob/master/Encodings.md#run-length-encoding--bit-packing-hybrid-rle--3).
+#[derive(Debug, PartialEq, Eq)]
+pub enum HybridEncoded<'a> {
+    /// An RLE run: the value (decoded from its little-endian bytes) and its number of
+    /// repetitions.
+    Rle(u32, usize),
+    /// A bit-packed run: the raw, LSB-first packed bytes.
+    Bitpacked(&'a [u8]),
+}
+
+/// Decoder of the runs (not the individual values) of the hybrid RLE/bit-packed encoding.
+#[derive(Debug, Clone)]
+pub struct Decoder<'a> {
+    values: &'a [u8],
+    num_bits: usize,
+}
+
+impl<'a> Decoder<'a> {
+    pub fn new(values: &'a [u8], num_bits: u32) -> Self {
+        Self {
+            values,
+            num_bits: num_bits as usize,
+        }
+    }
+}
+
+impl<'a> Iterator for Decoder<'a> {
+    type Item = HybridEncoded<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.values.is_empty() || self.num_bits == 0 {
+            return None;
+        }
+        let (indicator, consumed) = uleb128_decode(self.values);
+        self.values = &self.values[consumed..];
+
+        if indicator & 1 == 1 {
+            let num_groups = (indicator >> 1) as usize;
+            let num_bytes = (num_groups * self.num_bits).min(self.values.len());
+            let (packed, remaining) = self.values.split_at(num_bytes);
+            self.values = remaining;
+            Some(HybridEncoded::Bitpacked(packed))
+        } else {
+            let run_length = (indicator >> 1) as usize;
+            let num_bytes = ceil8(self.num_bits).min(self.values.len());
+            let (value_bytes, remaining) = self.values.split_at(num_bytes);
+            self.values = remaining;
+            let mut value = 0u32;
+            for (i, byte) in value_bytes.iter().enumerate() {
+                value |= (*byte as u32) << (8 * i);
+            }
+            Some(HybridEncoded::Rle(value, run_length))
+        }
+    }
+}
+
+/// Decoder of the individual `u32` values of the hybrid RLE/bit-packed encoding.
+#[derive(Debug, Clone)]
+pub struct HybridRleDecoder<'a> {
+    decoder: Decoder<'a>,
+    num_bits: usize,
+    remaining: usize,
+    buffer: VecDeque<u32>,
+}
+
+impl<'a> HybridRleDecoder<'a> {
+    pub fn new(values: &'a [u8], num_bits: u32, num_values: usize) -> Self {
+        Self {
+            decoder: Decoder::new(values, num_bits),
+            num_bits: num_bits as usize,
+            remaining: num_values,
+            buffer: VecDeque::new(),
+        }
+    }
+
+    /// Decodes the next run from `self.decoder` into `self.buffer`, clamped to `remaining`.
+    fn fill_buffer(&mut self) {
+        if self.num_bits == 0 {
+            // A bit width of 0 means the values are not encoded at all: the spec-legal case
+            // of a dictionary with a single entry implies `remaining` copies of index 0,
+            // with no run headers to read.
+            self.buffer
+                .extend(std::iter::repeat_n(0u32, self.remaining));
+            return;
+        }
+        match self.decoder.next() {
+            Some(HybridEncoded::Rle(value, length)) => {
+                let length = length.min(self.remaining);
+                self.buffer.extend(std::iter::repeat_n(value, length));
+            }
+            Some(HybridEncoded::Bitpacked(packed)) => {
+                let num_values = (packed.len() * 8 / self.num_bits.max(1)).min(self.remaining);
+                let mut values = vec![0u32; num_values];
+                unpack_bitpacked(packed, self.num_bits, &mut values);
+                self.buffer.extend(values);
+            }
+            None => {}
+        }
+    }
+}
+
+impl<'a> HybridRleDecoder<'a> {
+    /// Consumes `n` logical values without decoding them, returning the number actually
+    /// skipped (capped by the values remaining).
+    ///
+    /// Whole runs are skipped by decrementing an RLE run's count or advancing a bit-packed
+    /// run's bytes directly, without unpacking them; only the remainder of the run the skip
+    /// lands in (if any) is decoded, so skipping, say, 10k rows is O(runs) rather than
+    /// O(values).
+    pub fn skip_in_place(&mut self, n: usize) -> usize {
+        let n = n.min(self.remaining);
+
+        if self.num_bits == 0 {
+            // no run headers to skip over: just drop the equivalent number of implicit zeros.
+            self.buffer.clear();
+            self.remaining -= n;
+            return n;
+        }
+
+        let mut skipped = 0;
+
+        // drain whatever is already buffered.
+        while skipped < n {
+            if self.buffer.pop_front().is_some() {
+                skipped += 1;
+            } else {
+                break;
+            }
+        }
+
+        while skipped < n {
+            match self.decoder.next() {
+                Some(HybridEncoded::Rle(value, length)) => {
+                    if length <= n - skipped {
+                        skipped += length;
+                    } else {
+                        // the skip lands inside this run: only decode its remainder.
+                        let remainder = length - (n - skipped);
+                        self.buffer.extend(std::iter::repeat_n(value, remainder));
+                        skipped = n;
+                    }
+                }
+                Some(HybridEncoded::Bitpacked(packed)) => {
+                    let run_length = packed.len() * 8 / self.num_bits.max(1);
+                    if run_length <= n - skipped {
+                        skipped += run_length;
+                    } else {
+                        // the skip lands inside this run: only unpack its remainder.
+                        let start = n - skipped;
+                        let remainder = run_length - start;
+                        self.buffer.extend(
+                            (start..start + remainder)
+                                .map(|i| unpack_index(packed, self.num_bits, i)),
+                        );
+                        skipped = n;
+                    }
+                }
+                None => break,
+            }
+        }
+
+        self.remaining -= skipped;
+        skipped
+    }
+}
+
+impl<'a> Iterator for HybridRleDecoder<'a> {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        if self.buffer.is_empty() {
+            self.fill_buffer();
+        }
+        let value = self.buffer.pop_front()?;
+        self.remaining -= 1;
+        Some(value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.skip_in_place(n);
+        self.next()
+    }
+}
+
+/// A push-based target for the runs produced by [`HybridRleDecoder::gather_into`].
+///
+/// Implementors translate a whole run directly into their own output representation (e.g.
+/// `count` copies of `dict[value]`, or `count` set/unset bits of a bitmap) instead of pulling
+/// individual `u32`s out of an iterator. This moves the per-value branch between RLE and
+/// bit-packed runs out of the hot loop and into one dispatch per run.
+pub trait HybridRleGatherer {
+    /// Called once per RLE run, with the logical value and the number of repetitions.
+    fn gather_repeated(&mut self, value: u32, count: usize);
+
+    /// Called once per bit-packed run, with the raw LSB-first packed bytes and the number of
+    /// valid, `num_bits`-wide values packed into them.
+    fn gather_bitpacked(&mut self, packed: &[u8], num_values: usize, num_bits: u8);
+}
+
+impl<'a> HybridRleDecoder<'a> {
+    /// Drives `self` to completion, dispatching whole runs to `gatherer` instead of producing
+    /// an iterator of individual values.
+    pub fn gather_into<G: HybridRleGatherer>(mut self, gatherer: &mut G) {
+        // flush anything already pulled into `self.buffer` one value at a time: this only
+        // happens if `next` or `skip_in_place` was called before switching to the
+        // gather-based API.
+        let buffered = self.buffer.len().min(self.remaining);
+        for value in self.buffer.drain(..buffered) {
+            gatherer.gather_repeated(value, 1);
+        }
+        self.remaining -= buffered;
+
+        if self.num_bits == 0 {
+            // no run headers to dispatch: the whole column is one implicit run of zeros.
+            gatherer.gather_repeated(0, self.remaining);
+            return;
+        }
+
+        while self.remaining > 0 {
+            match self.decoder.next() {
+                Some(HybridEncoded::Rle(value, length)) => {
+                    let length = length.min(self.remaining);
+                    gatherer.gather_repeated(value, length);
+                    self.remaining -= length;
+                }
+                Some(HybridEncoded::Bitpacked(packed)) => {
+                    let num_values =
+                        (packed.len() * 8 / self.num_bits.max(1)).min(self.remaining);
+                    gatherer.gather_bitpacked(packed, num_values, self.num_bits as u8);
+                    self.remaining -= num_values;
+                }
+                None => break,
+            }
+        }
+    }
+}
+
+/// Decoder of the individual `bool` values of the hybrid RLE/bit-packed encoding, used when
+/// the maximum definition/repetition level is `1` and the runs are therefore bitmaps.
+#[derive(Debug, Clone)]
+pub struct HybridRleIter<'a> {
+    decoder: Decoder<'a>,
+    remaining: usize,
+    buffer: VecDeque<bool>,
+}
+
+/// The runs of a hybrid RLE/bit-packed-encoded definition/repetition level with maximum
+/// level `1` are bitmaps; this is the [`HybridRleIter`] specialized to `bool`.
+pub type HybridDecoderBitmapIter<'a> = HybridRleIter<'a>;
+
+impl<'a> HybridRleIter<'a> {
+    pub fn new(decoder: Decoder<'a>, num_values: usize) -> Self {
+        Self {
+            decoder,
+            remaining: num_values,
+            buffer: VecDeque::new(),
+        }
+    }
+
+    fn fill_buffer(&mut self) {
+        if self.decoder.num_bits == 0 {
+            // a bit width of 0 means there are no run headers: `remaining` implicit `false`s.
+            self.buffer
+                .extend(std::iter::repeat_n(false, self.remaining));
+            return;
+        }
+        match self.decoder.next() {
+            Some(HybridEncoded::Rle(value, length)) => {
+                let length = length.min(self.remaining);
+                self.buffer
+                    .extend(std::iter::repeat_n(value & 1 == 1, length));
+            }
+            Some(HybridEncoded::Bitpacked(packed)) => {
+                let num_values = (packed.len() * 8).min(self.remaining);
+                self.buffer
+                    .extend((0..num_values).map(|i| (packed[i / 8] >> (i % 8)) & 1 == 1));
+            }
+            None => {}
+        }
+    }
+}
+
+impl<'a> HybridRleIter<'a> {
+    /// Consumes `n` logical values without decoding them, mirroring
+    /// [`HybridRleDecoder::skip_in_place`] for the bitmap case.
+    pub fn skip_in_place(&mut self, n: usize) -> usize {
+        let n = n.min(self.remaining);
+
+        if self.decoder.num_bits == 0 {
+            // no run headers to skip over: just drop the equivalent number of implicit
+            // `false`s.
+            self.buffer.clear();
+            self.remaining -= n;
+            return n;
+        }
+
+        let mut skipped = 0;
+
+        while skipped < n {
+            if self.buffer.pop_front().is_some() {
+                skipped += 1;
+            } else {
+                break;
+            }
+        }
+
+        while skipped < n {
+            match self.decoder.next() {
+                Some(HybridEncoded::Rle(value, length)) => {
+                    if length <= n - skipped {
+                        skipped += length;
+                    } else {
+                        let remainder = length - (n - skipped);
+                        self.buffer
+                            .extend(std::iter::repeat_n(value & 1 == 1, remainder));
+                        skipped = n;
+                    }
+                }
+                Some(HybridEncoded::Bitpacked(packed)) => {
+                    let run_length = packed.len() * 8;
+                    if run_length <= n - skipped {
+                        skipped += run_length;
+                    } else {
+                        let start = n - skipped;
+                        let remainder = run_length - start;
+                        self.buffer.extend(
+                            (start..start + remainder)
+                                .map(|i| (packed[i / 8] >> (i % 8)) & 1 == 1),
+                        );
+                        skipped = n;
+                    }
+                }
+                None => break,
+            }
+        }
+
+        self.remaining -= skipped;
+        skipped
+    }
+}
+
+impl<'a> Iterator for HybridRleIter<'a> {
+    type Item = bool;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        if self.buffer.is_empty() {
+            self.fill_buffer();
+        }
+        let value = self.buffer.pop_front()?;
+        self.remaining -= 1;
+        Some(value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.skip_in_place(n);
+        self.next()
+    }
+}
+
+/// RLE-encodes `iterator` using the hybrid RLE/bit-packed encoding with `num_bits = 1`.
+pub fn encode_bool<W: Write, I: Iterator<Item = bool>>(
+    writer: &mut W,
+    iterator: I,
+) -> std::io::Result<()> {
+    let mut iterator = iterator.peekable();
+    while let Some(value) = iterator.next() {
+        let mut run_length: u64 = 1;
+        while iterator.peek() == Some(&value) {
+            iterator.next();
+            run_length += 1;
+        }
+        uleb128_encode(writer, run_length << 1)?;
+        writer.write_all(&[value as u8])?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roundtrip_bool() {
+        let values = vec![true, true, true, false, false, true, false, false, false];
+
+        let mut buffer = vec![];
+        encode_bool(&mut buffer, values.iter().cloned()).unwrap();
+
+        let decoder = Decoder::new(&buffer, 1);
+        let iter = HybridRleIter::new(decoder, values.len());
+        assert_eq!(iter.collect::<Vec<_>>(), values);
+    }
+
+    #[test]
+    fn hybrid_rle_decoder_rle_run() {
+        // indicator = 4 << 1 = 8 (run length 4, RLE), value = 2u8
+        let data = vec![8u8, 2];
+        let decoded = HybridRleDecoder::new(&data, 3, 4).collect::<Vec<_>>();
+        assert_eq!(decoded, vec![2, 2, 2, 2]);
+    }
+
+    #[test]
+    fn skip_in_place_matches_nth() {
+        // two RLE runs, then a bit-packed run: [1,1,1,1, 2,2, <8 bitpacked values>].
+        // indicator = (1 << 1) | 1 = 3 (bit-packed, 1 group of 8), 1 bit per value.
+        let data = vec![8u8, 1, 4u8, 2, 3u8, 0xA5];
+        let num_values = 4 + 2 + 8;
+
+        for skip in [0, 1, 4, 5, 6, 10, num_values, num_values + 5] {
+            let expected = HybridRleDecoder::new(&data, 1, num_values)
+                .collect::<Vec<_>>()
+                .into_iter()
+                .skip(skip)
+                .collect::<Vec<_>>();
+
+            let mut decoder = HybridRleDecoder::new(&data, 1, num_values);
+            let skipped = decoder.skip_in_place(skip);
+            assert_eq!(skipped, skip.min(num_values));
+            assert_eq!(decoder.collect::<Vec<_>>(), expected);
+        }
+    }
+
+    #[test]
+    fn bitmap_skip_in_place_matches_nth() {
+        let values = vec![
+            true, true, false, false, true, true, true, true, false, true, false,
+        ];
+        let mut buffer = vec![];
+        encode_bool(&mut buffer, values.iter().cloned()).unwrap();
+
+        for skip in [0, 1, 3, values.len(), values.len() + 2] {
+            let expected = values.iter().cloned().skip(skip).collect::<Vec<_>>();
+
+            let decoder = Decoder::new(&buffer, 1);
+            let mut iter = HybridRleIter::new(decoder, values.len());
+            let skipped = iter.skip_in_place(skip);
+            assert_eq!(skipped, skip.min(values.len()));
+            assert_eq!(iter.collect::<Vec<_>>(), expected);
+        }
+    }
+
+    #[test]
+    fn bitmap_skip_in_place_matches_nth_bitpacked() {
+        // an RLE run of 4 `true`s, then a bit-packed run: indicator = (1 << 1) | 1 = 3
+        // (bit-packed, 1 group of 8), 1 bit per value.
+        let data = vec![8u8, 1, 3u8, 0xA5];
+        let expected_all = vec![
+            true, true, true, true, true, false, true, false, false, true, false, true,
+        ];
+        let num_values = expected_all.len();
+
+        for skip in [0, 1, 4, 5, num_values, num_values + 2] {
+            let expected = expected_all.iter().cloned().skip(skip).collect::<Vec<_>>();
+
+            let decoder = Decoder::new(&data, 1);
+            let mut iter = HybridRleIter::new(decoder, num_values);
+            let skipped = iter.skip_in_place(skip);
+            assert_eq!(skipped, skip.min(num_values));
+            assert_eq!(iter.collect::<Vec<_>>(), expected);
+        }
+    }
+
+    struct VecGatherer(Vec<u32>);
+
+    impl HybridRleGatherer for VecGatherer {
+        fn gather_repeated(&mut self, value: u32, count: usize) {
+            self.0.extend(std::iter::repeat_n(value, count));
+        }
+
+        fn gather_bitpacked(&mut self, packed: &[u8], num_values: usize, num_bits: u8) {
+            let mut values = vec![0u32; num_values];
+            unpack_bitpacked(packed, num_bits as usize, &mut values);
+            self.0.extend(values);
+        }
+    }
+
+    #[test]
+    fn gather_into_matches_iterator() {
+        let data = vec![8u8, 2];
+        let expected = HybridRleDecoder::new(&data, 3, 4).collect::<Vec<_>>();
+
+        let mut gatherer = VecGatherer(vec![]);
+        HybridRleDecoder::new(&data, 3, 4).gather_into(&mut gatherer);
+
+        assert_eq!(gatherer.0, expected);
+    }
+
+    #[test]
+    fn gather_into_matches_iterator_bitpacked() {
+        // indicator = (1 << 1) | 1 = 3 (bit-packed, 1 group of 8), 1 bit per value
+        let data = vec![3u8, 0xA5];
+        let expected = HybridRleDecoder::new(&data, 1, 8).collect::<Vec<_>>();
+
+        let mut gatherer = VecGatherer(vec![]);
+        HybridRleDecoder::new(&data, 1, 8).gather_into(&mut gatherer);
+
+        assert_eq!(gatherer.0, expected);
+        assert_eq!(gatherer.0, vec![1, 0, 1, 0, 0, 1, 0, 1]);
+    }
+
+    #[test]
+    fn zero_bit_width_is_implicit_zeros() {
+        // bit width 0 (a dictionary with a single entry): no run headers are written, the
+        // `num_values` indices are implicitly all 0.
+        assert_eq!(
+            HybridRleDecoder::new(&[], 0, 5).collect::<Vec<_>>(),
+            vec![0, 0, 0, 0, 0]
+        );
+
+        let mut gatherer = VecGatherer(vec![]);
+        HybridRleDecoder::new(&[], 0, 5).gather_into(&mut gatherer);
+        assert_eq!(gatherer.0, vec![0, 0, 0, 0, 0]);
+
+        let mut decoder = HybridRleDecoder::new(&[], 0, 5);
+        assert_eq!(decoder.skip_in_place(2), 2);
+        assert_eq!(decoder.collect::<Vec<_>>(), vec![0, 0, 0]);
+
+        let decoder = Decoder::new(&[], 0);
+        assert_eq!(
+            HybridRleIter::new(decoder, 5).collect::<Vec<_>>(),
+            vec![false, false, false, false, false]
+        );
+
+        let decoder = Decoder::new(&[], 0);
+        let mut iter = HybridRleIter::new(decoder, 5);
+        assert_eq!(iter.skip_in_place(2), 2);
+        assert_eq!(iter.collect::<Vec<_>>(), vec![false, false, false]);
+    }
+}